@@ -0,0 +1,151 @@
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context};
+
+/// A single Advent of Code day, decoupled from how its input is fetched or
+/// which CLI flags select it. Implementors plug into [`run`] and [`bench`]
+/// to get uniform load → parse → solve → print/time behavior for free.
+pub trait Challenge {
+    type Input;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input>;
+    fn part1(input: &Self::Input) -> anyhow::Result<String>;
+    fn part2(input: &Self::Input) -> anyhow::Result<String>;
+}
+
+/// Makes sure the (example or real) input for `day` exists at `file` and
+/// reads it in.
+fn load(day: u32, file: &str, example: bool) -> anyhow::Result<String> {
+    let path = if example {
+        crate::input::ensure_example(day, file)?
+    } else {
+        crate::input::ensure_input(day, file)?;
+        file.to_string()
+    };
+    std::fs::read_to_string(&path).with_context(|| format!("reading {path}"))
+}
+
+/// Drives a [`Challenge`] end to end: makes sure the (example or real) input
+/// exists, reads and parses it, runs the requested part, and prints the
+/// result.
+pub fn run<C: Challenge>(day: u32, file: &str, example: bool, part: u8) -> anyhow::Result<()> {
+    let contents = load(day, file, example)?;
+    let input = C::parse(&contents)?;
+    let answer = match part {
+        1 => C::part1(&input)?,
+        2 => C::part2(&input)?,
+        other => bail!("unsupported part {other}, expected 1 or 2"),
+    };
+    println!("{answer}");
+    Ok(())
+}
+
+/// min/mean/median over a series of timed runs of the same stage.
+pub struct Timing {
+    pub min: Duration,
+    pub mean: Duration,
+    pub median: Duration,
+}
+
+impl Timing {
+    fn from_samples(mut samples: Vec<Duration>) -> Self {
+        samples.sort();
+        let min = samples[0];
+        let mean = samples.iter().sum::<Duration>() / samples.len() as u32;
+        let mid = samples.len() / 2;
+        let median = if samples.len().is_multiple_of(2) {
+            (samples[mid - 1] + samples[mid]) / 2
+        } else {
+            samples[mid]
+        };
+        Self { min, mean, median }
+    }
+
+    fn as_ms(&self) -> (f64, f64, f64) {
+        (
+            self.min.as_secs_f64() * 1000.0,
+            self.mean.as_secs_f64() * 1000.0,
+            self.median.as_secs_f64() * 1000.0,
+        )
+    }
+
+    fn to_json(&self) -> String {
+        let (min, mean, median) = self.as_ms();
+        format!(r#"{{"min_ms":{min:.3},"mean_ms":{mean:.3},"median_ms":{median:.3}}}"#)
+    }
+}
+
+/// Per-stage timings for one day, produced by [`bench`].
+pub struct BenchReport {
+    pub day: u32,
+    pub parse: Timing,
+    pub part1: Timing,
+    pub part2: Timing,
+}
+
+impl BenchReport {
+    pub fn print_table(&self) {
+        println!(
+            "{:<6} {:>10} {:>10} {:>10}",
+            "stage", "min(ms)", "mean(ms)", "median(ms)"
+        );
+        let stages = [
+            ("parse", &self.parse),
+            ("part1", &self.part1),
+            ("part2", &self.part2),
+        ];
+        for (label, timing) in stages {
+            let (min, mean, median) = timing.as_ms();
+            println!("{label:<6} {min:>10.3} {mean:>10.3} {median:>10.3}");
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        format!(
+            r#"{{"day":{},"parse":{},"part1":{},"part2":{}}}"#,
+            self.day,
+            self.parse.to_json(),
+            self.part1.to_json(),
+            self.part2.to_json(),
+        )
+    }
+}
+
+/// Times a [`Challenge`]'s parse, part 1, and part 2 stages separately.
+/// Parsing runs once; the part solvers (the expensive A* step for days that
+/// have one) run `iterations` times each so min/mean/median are meaningful.
+pub fn bench<C: Challenge>(
+    day: u32,
+    file: &str,
+    example: bool,
+    iterations: usize,
+) -> anyhow::Result<BenchReport> {
+    if iterations == 0 {
+        bail!("iterations must be at least 1");
+    }
+    let contents = load(day, file, example)?;
+
+    let parse_start = Instant::now();
+    let input = C::parse(&contents)?;
+    let parse = Timing::from_samples(vec![parse_start.elapsed()]);
+
+    let time = |part: fn(&C::Input) -> anyhow::Result<String>| -> anyhow::Result<Timing> {
+        let mut samples = Vec::with_capacity(iterations);
+        for _ in 0..iterations {
+            let start = Instant::now();
+            part(&input)?;
+            samples.push(start.elapsed());
+        }
+        Ok(Timing::from_samples(samples))
+    };
+
+    let part1 = time(C::part1)?;
+    let part2 = time(C::part2)?;
+
+    Ok(BenchReport {
+        day,
+        parse,
+        part1,
+        part2,
+    })
+}
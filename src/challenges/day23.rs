@@ -1,10 +1,17 @@
 use clap;
 
+use crate::input;
+
+/// Advent of Code day number, used to fetch the puzzle input.
+const DAY: u32 = 23;
+
 #[derive(clap::Args, Debug)]
 pub struct Args {
-    file: Option<String>,
+    #[arg(default_value_t = String::from("./inputs/day23/input.txt"))]
+    file: String,
 }
 
 pub fn entrypoint(args: &Args) {
+    input::ensure_input(DAY, &args.file).expect("failed to acquire puzzle input");
     println!("{:?}", args.file);
 }
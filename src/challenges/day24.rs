@@ -1,6 +1,12 @@
-use clap::Parser;
+use anyhow::Context;
+use fixedbitset::FixedBitSet;
 use pathfinding::directed::astar;
-use std::{fmt, fs, str};
+
+use crate::challenge::Challenge;
+use crate::parsers;
+
+/// Advent of Code day number, used to fetch the puzzle input and example.
+pub(crate) const DAY: u32 = 24;
 
 const COLLIDES_FLAG: u8 = 0b1000_0000u8;
 const NORTH_FLAG: u8 = 0b0000_0001u8;
@@ -88,22 +94,38 @@ impl Point {
     }
 }
 
-fn find_first_empty(line: &str) -> Result<usize, &str> {
-    for (i, c) in line.chars().enumerate() {
-        if c == '.' {
-            return Ok(i);
-        }
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
     }
-    return Err("No blanks in line");
+}
+
+fn lcm(a: usize, b: usize) -> usize {
+    a / gcd(a, b) * b
+}
+
+fn flags_for(c: char) -> Option<u8> {
+    PARSABLE
+        .iter()
+        .find(|obstacle_type| obstacle_type.tile_char == c)
+        .map(|obstacle_type| obstacle_type.tile_flags)
 }
 
 #[derive(Debug)]
-struct Level {
+pub struct Level {
     tiles: Vec<u8>,
     width: usize,
     height: usize,
     enter: Point,
     exit: Point,
+    /// Blizzards are periodic, so the whole field repeats every `period`
+    /// minutes (the LCM of the inner width and height). `grids[t]` is the
+    /// collision bitset for `t`, letting `validate_point` do an O(1) lookup
+    /// instead of recomputing `future_tile` for every candidate point.
+    period: usize,
+    grids: Vec<FixedBitSet>,
 }
 
 impl Level {
@@ -114,9 +136,34 @@ impl Level {
             height,
             enter: Point { x: 0, y: 0, t: 0 },
             exit: Point { x: 0, y: 0, t: 0 },
+            period: 1,
+            grids: Vec::new(),
         }
     }
 
+    /// Materializes one collision bitset per minute of the blizzard period.
+    /// The border walls (including the `enter`/`exit` rows) are static, so
+    /// they end up identical in every slice; only the interior blizzard
+    /// cells vary between them.
+    fn build_grids(&mut self) {
+        let inner_width = self.width - 2;
+        let inner_height = self.height - 2;
+        self.period = lcm(inner_width, inner_height);
+        self.grids = (0..self.period)
+            .map(|t| {
+                let mut grid = FixedBitSet::with_capacity(self.width * self.height);
+                for y in 0..self.height {
+                    for x in 0..self.width {
+                        if self.future_tile(x, y, t) & COLLIDES_FLAG == COLLIDES_FLAG {
+                            grid.insert(y * self.width + x);
+                        }
+                    }
+                }
+                grid
+            })
+            .collect();
+    }
+
     fn future_tile(&self, x: usize, y: usize, t: usize) -> u8 {
         if x == 0 || y == 0 || x == self.width - 1 || y == self.height - 1 {
             // walls are not affected by the temporal dimension
@@ -132,37 +179,40 @@ impl Level {
         let v_mod = t % inner_height;
         // calculate coordinates for each direction according to the offsets
         // and switch back to the original coordinate system
-        let north_y = ((inner_height + inner_height + v + v_mod) % inner_height);
-        let south_y = ((inner_height + inner_height + v - v_mod) % inner_height);
-        let east_x = ((inner_width + inner_width + u - u_mod) % inner_width);
-        let west_x = ((inner_width + inner_width + u + u_mod) % inner_width);
+        let north_y = (inner_height + inner_height + v + v_mod) % inner_height;
+        let south_y = (inner_height + inner_height + v - v_mod) % inner_height;
+        let east_x = (inner_width + inner_width + u - u_mod) % inner_width;
+        let west_x = (inner_width + inner_width + u + u_mod) % inner_width;
         // get the tile data
         let south_bits = self.get_tile(x, south_y + 1) & SOUTH_FLAG;
         let north_bits = self.get_tile(x, north_y + 1) & NORTH_FLAG;
         let west_bits = self.get_tile(west_x + 1, y) & WEST_FLAG;
         let east_bits = self.get_tile(east_x + 1, y) & EAST_FLAG;
         let tile = north_bits | east_bits | south_bits | west_bits;
-        // let tile = west_bits;
         if tile > 0 {
-            return tile | COLLIDES_FLAG;
+            tile | COLLIDES_FLAG
         } else {
-            return 0x00;
+            0x00
         }
     }
 
     fn validate_point(&self, point: &Point) -> bool {
-        let tile = self.future_tile(point.x, point.y, point.t);
-        tile & COLLIDES_FLAG != COLLIDES_FLAG
-    }
-
-    fn is_exit_point(&self, point: &Point) -> bool {
-        point.distance(&self.exit) == 0
+        !self.grids[point.t % self.period].contains(point.y * self.width + point.x)
     }
 
     fn successors(&self, point: &Point) -> Vec<(Point, usize)> {
-        let moves: Vec<(Point, usize)> = point.moves();
-        moves
+        // Collapse node identity to (x, y, t % period): since blizzard
+        // positions repeat with that period, two points differing only by a
+        // whole number of periods are equivalent states, and A* should not
+        // explore them separately.
+        let period = self.period;
+        point
+            .moves()
             .into_iter()
+            .map(|(mut p, cost)| {
+                p.t %= period;
+                (p, cost)
+            })
             .filter(|p| self.validate_point(&p.0))
             .collect::<Vec<(Point, usize)>>()
     }
@@ -171,116 +221,85 @@ impl Level {
         self.tiles[(y * self.width) + x]
     }
 
-    fn set_tile(&mut self, x: usize, y: usize, val: u8) {
-        self.tiles[(y * self.width) + x] = val;
-    }
-
-    fn at(&self, t: usize) -> Self {
-        let mut state = Self::new(self.width, self.height);
-        state.enter = self.enter.clone();
-        state.exit = self.exit.clone();
-        for x in 0..(self.width) {
-            for y in 0..(self.height) {
-                state.set_tile(x, y, self.future_tile(x, y, t))
-            }
-        }
-        state
-    }
-
-    fn solve(&self) -> Option<(Vec<Point>, usize)> {
+    /// Runs A* from an arbitrary `start` (which may carry a nonzero `t`, e.g.
+    /// the arrival time of a previous leg) to `goal`. Blizzard positions are
+    /// a pure function of `t`, so chaining legs just means feeding one leg's
+    /// final point in as the next leg's start.
+    fn solve_from(&self, start: &Point, goal: &Point) -> Option<(Vec<Point>, usize)> {
         astar::astar(
-            &self.enter,
+            start,
             |p| self.successors(p),
-            |p| self.exit.distance(p),
-            |p| self.is_exit_point(p),
+            |p| goal.distance(p),
+            |p| p.distance(goal) == 0,
         )
     }
 
-    fn to_ascii(&self) -> Vec<char> {
-        let mut buf: Vec<char> = vec!['\0'; self.width * self.height + self.height];
-        let mut offset: usize = 0;
-        for (i, tile) in self.tiles.iter().enumerate() {
-            let str_i = i + offset;
-            buf[str_i] = '.';
-            for obstacle_type in PARSABLE {
-                if tile & obstacle_type.tile_flags == obstacle_type.tile_flags {
-                    match buf[str_i] {
-                        '.' | '#' => buf[str_i] = obstacle_type.tile_char,
-                        '^' | '>' | 'V' | '<' => buf[str_i] = '2',
-                        '2' => buf[str_i] = '3',
-                        '3' => buf[str_i] = '4',
-                        _ => buf[str_i] = '?',
-                    }
-                }
-            }
-            if i > 0 && (i + 1) % self.width == 0 && i < self.tiles.len() - 1 {
-                buf[str_i + 1] = '\n';
-                offset += 1;
-            }
-        }
-        buf
+    fn from_input(input: &str) -> anyhow::Result<Self> {
+        let grid = parsers::grid(input, flags_for)?;
+        let mut state = Self::new(grid.width, grid.height);
+        state.tiles = grid.tiles;
+        state.enter = Point {
+            x: grid.enter.0,
+            y: grid.enter.1,
+            t: 0,
+        };
+        state.exit = Point {
+            x: grid.exit.0,
+            y: grid.exit.1,
+            t: 0,
+        };
+        state.build_grids();
+        Ok(state)
     }
+}
 
-    fn print_solution(&self) {
-        let (path, cost) = self.solve().unwrap();
-        for (i, point) in path.iter().enumerate() {
-            let mut ascii = self.at(i).to_ascii();
-            ascii[point.y * (self.width + 1) + point.x] = 'E';
-            let s = ascii.iter().collect::<String>();
-            println!("\n@ t={}, {:?}\n{}", i, point, s);
-        }
+/// Marker type hanging the [`Challenge`] impl off Day 24's [`Level`].
+pub struct Day24;
+
+impl Challenge for Day24 {
+    type Input = Level;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        Level::from_input(input)
     }
 
-    fn parse(path: &String) -> Self {
-        let input = fs::read_to_string(path).expect("I/O error");
-        let mut width: usize = 0;
-        let mut height: usize = 0;
-        for line in input.lines() {
-            height += 1;
-            if line.len() > width {
-                width = line.len();
-            }
-        }
-        let mut state = Self::new(width, height);
-        for (y, line) in input.lines().enumerate() {
-            if y == 0 {
-                let start_x = find_first_empty(line).unwrap();
-                state.enter.x = start_x;
-            }
-            if y == height - 1 {
-                let exit_x = find_first_empty(line).unwrap();
-                state.exit.x = exit_x;
-                state.exit.y = y;
-            }
-            for (x, c) in line.chars().enumerate() {
-                for obstacle_type in PARSABLE {
-                    if c == obstacle_type.tile_char {
-                        state.set_tile(x, y, obstacle_type.tile_flags);
-                        break;
-                    }
-                }
-            }
-        }
-        state
+    fn part1(input: &Self::Input) -> anyhow::Result<String> {
+        let (_, cost) = input
+            .solve_from(&input.enter, &input.exit)
+            .context("no path from start to goal")?;
+        Ok(format!("{cost} minutes"))
     }
-}
 
-impl fmt::Display for Level {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let s = self.to_ascii().iter().collect::<String>();
-        write!(f, "{}", s)
+    fn part2(input: &Self::Input) -> anyhow::Result<String> {
+        let (leg1, cost1) = input
+            .solve_from(&input.enter, &input.exit)
+            .context("no path for leg 1 (start -> goal)")?;
+        let leg2_start = leg1.last().unwrap().clone();
+        let (leg2, cost2) = input
+            .solve_from(&leg2_start, &input.enter)
+            .context("no path for leg 2 (goal -> start)")?;
+        let leg3_start = leg2.last().unwrap().clone();
+        let (_, cost3) = input
+            .solve_from(&leg3_start, &input.exit)
+            .context("no path for leg 3 (start -> goal)")?;
+
+        Ok(format!(
+            "{} minutes total (legs: {cost1}, {cost2}, {cost3})",
+            cost1 + cost2 + cost3
+        ))
     }
 }
 
 #[derive(clap::Args, Debug)]
 pub struct Args {
     #[arg(default_value_t = String::from("./inputs/day24/input.txt"))]
-    file: String,
-}
+    pub(crate) file: String,
+
+    /// Fetch and solve against the puzzle's example input instead
+    #[arg(long)]
+    pub(crate) example: bool,
 
-pub fn entrypoint(args: &Args) {
-    let level = Level::parse(&args.file);
-    println!("Loaded map:\n{}", level);
-    println!("\nStart {:?}, Goal {:?}", level.enter, level.exit);
-    level.print_solution();
+    /// Which part of the puzzle to solve
+    #[arg(short, long, default_value_t = 1)]
+    pub(crate) part: u8,
 }
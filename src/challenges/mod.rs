@@ -0,0 +1,2 @@
+pub mod day23;
+pub mod day24;
@@ -0,0 +1,88 @@
+use std::{env, fs, path::Path};
+
+use anyhow::{bail, Context, Result};
+
+/// Makes sure the puzzle input for `day` exists at `path`, fetching it from
+/// Advent of Code with the `AOC_COOKIE` session cookie if it's missing.
+pub fn ensure_input(day: u32, path: &str) -> Result<()> {
+    if Path::new(path).exists() {
+        return Ok(());
+    }
+    let cookie = aoc_cookie()?;
+    let url = format!("https://adventofcode.com/2022/day/{day}/input");
+    let body = fetch(&url, &cookie)?;
+    write(path, &body)
+}
+
+/// Makes sure the example input for `day` exists alongside `path` as
+/// `input.example.txt`, fetching it from the day's puzzle page if it's
+/// missing, and returns the path it lives at.
+pub fn ensure_example(day: u32, path: &str) -> Result<String> {
+    let example_path = sibling_path(path, "input.example.txt");
+    if Path::new(&example_path).exists() {
+        return Ok(example_path);
+    }
+    let cookie = aoc_cookie()?;
+    let url = format!("https://adventofcode.com/2022/day/{day}");
+    let page = fetch(&url, &cookie)?;
+    let example = extract_example(&page)?;
+    write(&example_path, &example)?;
+    Ok(example_path)
+}
+
+fn aoc_cookie() -> Result<String> {
+    env::var("AOC_COOKIE").context("input is missing and AOC_COOKIE is not set to fetch it")
+}
+
+fn fetch(url: &str, cookie: &str) -> Result<String> {
+    let client = reqwest::blocking::Client::new();
+    let resp = client
+        .get(url)
+        .header("Cookie", format!("session={cookie}"))
+        .send()
+        .with_context(|| format!("requesting {url}"))?;
+    if !resp.status().is_success() {
+        bail!("unexpected status {} from {url}", resp.status());
+    }
+    resp.text().with_context(|| format!("reading body of {url}"))
+}
+
+fn write(path: &str, body: &str) -> Result<()> {
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent).with_context(|| format!("creating {}", parent.display()))?;
+    }
+    fs::write(path, body).with_context(|| format!("writing {path}"))
+}
+
+fn sibling_path(path: &str, file_name: &str) -> String {
+    match Path::new(path).parent() {
+        Some(parent) => parent.join(file_name).to_string_lossy().into_owned(),
+        None => file_name.to_string(),
+    }
+}
+
+/// Pulls the first `<pre><code>` block following a "For example" paragraph
+/// out of a day's puzzle page HTML.
+fn extract_example(page: &str) -> Result<String> {
+    let marker_at = page
+        .find("For example")
+        .context("no \"For example\" paragraph found on puzzle page")?;
+    let block_start = page[marker_at..]
+        .find("<pre><code>")
+        .context("no <pre><code> block after \"For example\"")?
+        + marker_at
+        + "<pre><code>".len();
+    let block_end = page[block_start..]
+        .find("</code></pre>")
+        .context("unterminated <pre><code> block")?
+        + block_start;
+    Ok(unescape_html(&page[block_start..block_end]))
+}
+
+fn unescape_html(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
@@ -1,6 +1,10 @@
+use clap::builder::TypedValueParser as _;
 use clap::Parser;
 
+mod challenge;
 mod challenges;
+mod input;
+mod parsers;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
@@ -8,6 +12,23 @@ struct Cli {
     /// Challenge Day
     #[command(subcommand)]
     command: Command,
+
+    /// Time parse/part1/part2 instead of solving once
+    #[arg(long, global = true)]
+    bench: bool,
+
+    /// Number of timed runs of each part when benchmarking
+    #[arg(
+        long,
+        global = true,
+        default_value_t = 10,
+        value_parser = clap::value_parser!(u64).range(1..).map(|v| v as usize)
+    )]
+    iterations: usize,
+
+    /// Also print the benchmark summary as JSON
+    #[arg(long, global = true)]
+    json: bool,
 }
 
 #[derive(clap::Subcommand, Debug)]
@@ -17,10 +38,43 @@ enum Command {
     Day23(challenges::day23::Args),
 }
 
-fn main() {
+fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
     match &cli.command {
-        Command::Day24(args) => challenges::day24::entrypoint(args),
-        Command::Day23(args) => challenges::day23::entrypoint(args),
+        Command::Day24(args) => {
+            if cli.bench {
+                run_bench::<challenges::day24::Day24>(
+                    &cli,
+                    challenges::day24::DAY,
+                    &args.file,
+                    args.example,
+                )
+            } else {
+                challenge::run::<challenges::day24::Day24>(
+                    challenges::day24::DAY,
+                    &args.file,
+                    args.example,
+                    args.part,
+                )
+            }
+        }
+        Command::Day23(args) => {
+            challenges::day23::entrypoint(args);
+            Ok(())
+        }
+    }
+}
+
+fn run_bench<C: challenge::Challenge>(
+    cli: &Cli,
+    day: u32,
+    file: &str,
+    example: bool,
+) -> anyhow::Result<()> {
+    let report = challenge::bench::<C>(day, file, example, cli.iterations)?;
+    report.print_table();
+    if cli.json {
+        println!("{}", report.to_json());
     }
+    Ok(())
 }
@@ -0,0 +1,57 @@
+//! Shared `nom` combinators for the bits of puzzle input that look the same
+//! across days, so each day's module only needs to supply the parts that
+//! are actually day-specific (here, the character-to-flags mapping).
+
+use anyhow::Context;
+use nom::{
+    character::complete::{line_ending, none_of},
+    multi::{many1, separated_list1},
+    IResult,
+};
+
+/// A rectangular grid of tile flags, with the single open cell in the first
+/// and last rows recorded as `enter`/`exit`.
+pub struct Grid {
+    pub tiles: Vec<u8>,
+    pub width: usize,
+    pub height: usize,
+    pub enter: (usize, usize),
+    pub exit: (usize, usize),
+}
+
+/// Parses a rectangular character grid, mapping each character to its flag
+/// byte via `flags_for`, and locates the open (`.`) cell in the first and
+/// last rows as `enter`/`exit`.
+pub fn grid(input: &str, flags_for: impl Fn(char) -> Option<u8>) -> anyhow::Result<Grid> {
+    let (_, rows) = rows(input).map_err(|e| anyhow::anyhow!("parse error: {e}"))?;
+    let height = rows.len();
+    let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+
+    let mut tiles = vec![0u8; width * height];
+    for (y, row) in rows.iter().enumerate() {
+        for (x, &c) in row.iter().enumerate() {
+            let flags =
+                flags_for(c).with_context(|| format!("unrecognized tile '{c}' at ({x}, {y})"))?;
+            tiles[y * width + x] = flags;
+        }
+    }
+
+    let enter_x = open_cell(&rows[0]).context("no open cell in first row")?;
+    let exit_x = open_cell(&rows[height - 1]).context("no open cell in last row")?;
+
+    Ok(Grid {
+        tiles,
+        width,
+        height,
+        enter: (enter_x, 0),
+        exit: (exit_x, height - 1),
+    })
+}
+
+fn open_cell(row: &[char]) -> Option<usize> {
+    row.iter().position(|&c| c == '.')
+}
+
+fn rows(input: &str) -> IResult<&str, Vec<Vec<char>>> {
+    separated_list1(line_ending, many1(none_of("\r\n")))(input)
+}